@@ -0,0 +1,91 @@
+/// # Histogram
+///
+/// A frequency distribution over equal-width bins spanning a dataset's range.
+///
+/// Before binning, points that fall outside `[Q1 - 1.5*IQR, Q3 + 1.5*IQR]` are dropped, so a few
+/// extreme values don't stretch the range and flatten the histogram into one bin.
+///
+/// `bins[i]` holds the count of points in `[boundaries[i], boundaries[i + 1])` (the last bin is
+/// closed on both ends).
+pub struct Histogram {
+    pub bins: Vec<usize>,
+    pub boundaries: Vec<f64>,
+}
+
+impl Histogram {
+    /// Builds a histogram of `data` with `bin_count` equal-width bins.
+    ///
+    /// Panics if dataset is empty.
+    ///
+    /// ## Example
+    /// ```
+    /// use kirstine::histogram::Histogram;
+    ///
+    /// let data = vec![1.0, 2.0, 2.0, 3.0, 4.0, 4.0, 4.0, 5.0];
+    /// let histogram = Histogram::new(&data, 4);
+    /// assert_eq!(histogram.bins, vec![1, 2, 1, 4]);
+    /// assert_eq!(histogram.boundaries, vec![1.0, 2.0, 3.0, 4.0, 5.0]);
+    /// ```
+    pub fn new(data: &[f64], bin_count: usize) -> Histogram {
+        let (q1, _, q3) = super::quartiles(data);
+        let iqr = q3 - q1;
+        let lower_fence = q1 - 1.5 * iqr;
+        let upper_fence = q3 + 1.5 * iqr;
+        let filtered: Vec<f64> = data
+            .iter()
+            .cloned()
+            .filter(|x| *x >= lower_fence && *x <= upper_fence)
+            .collect();
+
+        let (_, _, (smallest, largest)) = super::range(&filtered);
+        let min = *smallest;
+        let max = *largest;
+        let width = (max - min) / bin_count as f64;
+        let mut boundaries: Vec<f64> = (0..=bin_count).map(|i| min + width * i as f64).collect();
+        // `min + width * bin_count` can round to slightly less than `max`, which would make
+        // `to_bin(max)` reject the true maximum. Pin the last boundary to the real max instead.
+        *boundaries.last_mut().unwrap() = max;
+
+        let mut histogram = Histogram {
+            bins: vec![0; bin_count],
+            boundaries,
+        };
+        for x in filtered.iter() {
+            if let Some(i) = histogram.to_bin(*x) {
+                histogram.bins[i] += 1;
+            }
+        }
+        histogram
+    }
+
+    /// Finds which bin `value` falls into, clamping the maximum value into the last bin.
+    ///
+    /// Returns `None` if `value` falls outside the histogram's range.
+    pub fn to_bin(&self, value: f64) -> Option<usize> {
+        let min = self.boundaries[0];
+        let max = *self.boundaries.last().unwrap();
+        if value < min || value > max {
+            return None;
+        }
+        let bin_count = self.boundaries.len() - 1;
+        let width = (max - min) / bin_count as f64;
+        let index = ((value - min) / width).floor() as usize;
+        Some(index.min(bin_count - 1))
+    }
+}
+
+#[test]
+fn histogram_rejects_outliers_test() {
+    let data = vec![1.0, 2.0, 3.0, 4.0, 5.0, 100.0];
+    let histogram = Histogram::new(&data, 2);
+    assert_eq!(histogram.boundaries, vec![1.0, 3.0, 5.0]);
+    assert_eq!(histogram.bins, vec![2, 3]);
+}
+
+#[test]
+fn histogram_keeps_max_when_boundary_rounds_low_test() {
+    let data = vec![0.2, 0.3, 0.4, 0.5, 0.6, 0.7000000000000001];
+    let histogram = Histogram::new(&data, 2);
+    assert_eq!(histogram.bins.iter().sum::<usize>(), data.len());
+    assert_eq!(histogram.to_bin(0.7000000000000001), Some(1));
+}
@@ -1,9 +1,25 @@
+pub mod histogram;
 pub mod population;
 pub mod sample;
+pub mod stats;
 
-use std::cmp::Ordering::Less;
+use std::cmp::Ordering::{Greater, Less};
 use std::collections::HashMap;
 
+pub use stats::Stats;
+
+/// Sorts `data` in place using a total ordering over `f64` (via `f64::total_cmp`), instead of
+/// `partial_cmp`, so that `NaN` values sort consistently rather than being silently treated as
+/// `Less`. Used internally by `median`, `percentile`, `quartiles` and the `Stats` trait.
+pub(crate) fn local_sort(data: &mut [f64]) {
+    data.sort_by(|a, b| a.total_cmp(b));
+}
+
+/// Returns `true` if any value in `data` is `NaN`.
+fn contains_nan(data: &[f64]) -> bool {
+    data.iter().any(|x| x.is_nan())
+}
+
 /// # Arithmetic Mean
 ///
 /// Calculates the mean, or the average, of a vector of floats.
@@ -17,9 +33,168 @@ use std::collections::HashMap;
 /// let data = vec![1.0, 3.0, 3.0, 2.0, 1.0];
 /// assert_eq!(kirstine::mean(&data), 2.0);
 /// ```
-pub fn mean(data: &Vec<f64>) -> f64 {
-    let sum: f64 = data.iter().sum();
-    sum / data.len() as f64
+pub fn mean(data: &[f64]) -> f64 {
+    data.mean()
+}
+
+/// # Sum
+///
+/// Adds up a dataset using Neumaier's improved Kahan summation, which keeps a running
+/// compensation term for the error introduced by each addition. This is considerably more
+/// accurate than a naive `iter().sum()` for large datasets or values of very different
+/// magnitudes, matching the accuracy-over-speed philosophy of Rust's libtest `Stats::sum`.
+///
+/// ## Example
+/// ```
+/// let data = vec![1.0, 3.0, 3.0, 2.0, 1.0];
+/// assert_eq!(kirstine::sum(&data), 10.0);
+/// ```
+pub fn sum(data: &[f64]) -> f64 {
+    data.sum()
+}
+
+/// # Percentile
+///
+/// Calculates the value at a given percentile using linear interpolation between the two nearest
+/// order statistics, the same approach used by Rust's libtest `Stats::percentile`.
+///
+/// `pct` is clamped to the range `[0, 100]`. `pct == 0.0` returns the minimum, and `pct == 100.0`
+/// returns the maximum.
+///
+/// The function needs to clone and sort the dataset which is expensive, so if you know that your
+/// dataset is sorted, then use `kirstine::percentile_from_sorted` instead.
+///
+/// Panics if dataset is empty.
+///
+/// `NaN` values sort via `f64::total_cmp` (a positive `NaN` sorts after every number), so a
+/// contaminated dataset is ordered deterministically, but the result can itself be `NaN`; use
+/// `kirstine::try_percentile` if you need to detect that instead.
+///
+/// ## Example
+/// ```
+/// let data = vec![3.0, 1.0, 4.0, 2.0];
+/// assert_eq!(kirstine::percentile(&data, 0.0), 1.0);
+/// assert_eq!(kirstine::percentile(&data, 100.0), 4.0);
+/// assert_eq!(kirstine::percentile(&data, 50.0), 2.5);
+/// ```
+pub fn percentile(data: &[f64], pct: f64) -> f64 {
+    data.percentile(pct)
+}
+
+/// # Percentile, checked for `NaN`
+///
+/// Like `kirstine::percentile`, but returns `None` if `data` contains `NaN` instead of silently
+/// computing a meaningless statistic.
+///
+/// Panics if dataset is empty.
+pub fn try_percentile(data: &[f64], pct: f64) -> Option<f64> {
+    if contains_nan(data) {
+        return None;
+    }
+    Some(percentile(data, pct))
+}
+
+/// # Percentile from sorted vector
+///
+/// Faster version of `kirstine::percentile` that can be used if you know that your dataset is
+/// already sorted.
+///
+/// Panics if dataset is empty.
+pub fn percentile_from_sorted(data: &[f64], pct: f64) -> f64 {
+    if data.is_empty() {
+        panic!("Can not find percentile of empty list");
+    }
+    if data.len() == 1 {
+        return data[0];
+    }
+    let pct = pct.clamp(0.0, 100.0);
+    let r = (pct / 100.0) * (data.len() - 1) as f64;
+    let lo = r.floor() as usize;
+    let hi = r.ceil() as usize;
+    data[lo] + (r - lo as f64) * (data[hi] - data[lo])
+}
+
+/// # Quartiles
+///
+/// Calculates the first, second and third quartiles (`Q1`, `Q2`, `Q3`) of a dataset, i.e. the
+/// 25th, 50th and 75th percentiles.
+///
+/// Panics if dataset is empty.
+///
+/// ## Example
+/// ```
+/// let data = vec![6.0, 7.0, 15.0, 36.0, 39.0, 40.0, 41.0, 42.0, 43.0, 47.0, 49.0];
+/// let (q1, q2, q3) = kirstine::quartiles(&data);
+/// assert_eq!(q1, 25.5);
+/// assert_eq!(q2, 40.0);
+/// assert_eq!(q3, 42.5);
+/// ```
+pub fn quartiles(data: &[f64]) -> (f64, f64, f64) {
+    let mut copy = data.to_vec();
+    local_sort(&mut copy);
+    let q1 = percentile_from_sorted(&copy, 25.0);
+    let q2 = percentile_from_sorted(&copy, 50.0);
+    let q3 = percentile_from_sorted(&copy, 75.0);
+    (q1, q2, q3)
+}
+
+/// # Quartiles, checked for `NaN`
+///
+/// Like `kirstine::quartiles`, but returns `None` if `data` contains `NaN` instead of silently
+/// computing meaningless statistics.
+///
+/// Panics if dataset is empty.
+pub fn try_quartiles(data: &[f64]) -> Option<(f64, f64, f64)> {
+    if contains_nan(data) {
+        return None;
+    }
+    Some(quartiles(data))
+}
+
+/// # Interquartile range
+///
+/// Calculates the interquartile range (`Q3 - Q1`), a measure of statistical spread that, unlike
+/// `range` and `variance`, is robust against outliers.
+///
+/// Panics if dataset is empty.
+///
+/// ## Example
+/// ```
+/// let data = vec![6.0, 7.0, 15.0, 36.0, 39.0, 40.0, 41.0, 42.0, 43.0, 47.0, 49.0];
+/// assert_eq!(kirstine::iqr(&data), 17.0);
+/// ```
+pub fn iqr(data: &[f64]) -> f64 {
+    data.iqr()
+}
+
+/// # Winsorize
+///
+/// Clips the tails of a dataset in place, replacing any value below the `pct` percentile with
+/// that percentile, and any value above the `100 - pct` percentile with that percentile.
+///
+/// This is a preprocessing step that lets outlier-sensitive functions, like `mean`, `variance`
+/// and `standard_deviation`, become robust against a few wild samples without discarding data
+/// points.
+///
+/// Panics if dataset is empty.
+///
+/// ## Example
+/// ```
+/// let mut data = vec![1.0, 2.0, 3.0, 4.0, 100.0];
+/// kirstine::winsorize(&mut data, 20.0);
+/// assert_eq!(data[0], 1.8);
+/// assert!((data[4] - 23.2).abs() < 0.0000001);
+/// ```
+pub fn winsorize(data: &mut [f64], pct: f64) {
+    let lower_cut = percentile(data, pct);
+    let upper_cut = percentile(data, 100.0 - pct);
+    for x in data.iter_mut() {
+        if *x < lower_cut {
+            *x = lower_cut;
+        } else if *x > upper_cut {
+            *x = upper_cut;
+        }
+    }
 }
 
 /// # Arithmetic Median
@@ -29,6 +204,10 @@ pub fn mean(data: &Vec<f64>) -> f64 {
 /// The function needs to clone and sort the dataset which is expensive, so if you know that your
 /// dataset is sorted, then use `kirstine::median_from_sorted` instead.
 ///
+/// `NaN` values sort via `f64::total_cmp` (a positive `NaN` sorts after every number), so a
+/// contaminated dataset is ordered deterministically, but the result can itself be `NaN`; use
+/// `kirstine::try_median` if you need to detect that instead.
+///
 /// Panics if dataset is empty.
 ///
 /// ## Example
@@ -39,10 +218,21 @@ pub fn mean(data: &Vec<f64>) -> f64 {
 /// let data = vec![2.0, 5.0, 3.0, 1.0];
 /// assert_eq!(kirstine::median(&data), 2.5);
 /// ```
-pub fn median(data: &Vec<f64>) -> f64 {
-    let mut copy = data.clone();
-    copy.sort_by(|m, n| m.partial_cmp(n).unwrap_or(Less));
-    median_from_sorted(&copy)
+pub fn median(data: &[f64]) -> f64 {
+    data.median()
+}
+
+/// # Median, checked for `NaN`
+///
+/// Like `kirstine::median`, but returns `None` if `data` contains `NaN` instead of silently
+/// computing a meaningless statistic.
+///
+/// Panics if dataset is empty.
+pub fn try_median(data: &[f64]) -> Option<f64> {
+    if contains_nan(data) {
+        return None;
+    }
+    Some(median(data))
 }
 
 /// # Median from sorted vector
@@ -51,20 +241,50 @@ pub fn median(data: &Vec<f64>) -> f64 {
 /// sorted.
 ///
 /// Panics if dataset is empty.
-pub fn median_from_sorted(data: &Vec<f64>) -> f64 {
+pub fn median_from_sorted(data: &[f64]) -> f64 {
     if data.is_empty() {
         panic!("Can not find median of empty list");
     }
     if data.len() % 2 == 1 {
-        data[((data.len() - 1) / 2) as usize]
+        data[(data.len() - 1) / 2]
     } else {
-        let upper_index = data.len() / 2 as usize;
+        let upper_index = data.len() / 2;
         let lower_index = upper_index - 1;
         let sum = data[upper_index] + data[lower_index];
         sum / 2.0
     }
 }
 
+/// # Median absolute deviation
+///
+/// A robust measure of dispersion: the median of the absolute deviations from the dataset's
+/// median. Unlike `variance` and `standard_deviation`, a few extreme values cannot dominate the
+/// result.
+///
+/// Panics if dataset is empty.
+///
+/// ## Example
+/// ```
+/// let data = vec![1.0, 2.0, 3.0, 4.0, 100.0];
+/// assert_eq!(kirstine::median_absolute_deviation(&data), 1.0);
+/// ```
+pub fn median_absolute_deviation(data: &[f64]) -> f64 {
+    let m = median(data);
+    let deviations: Vec<f64> = data.iter().map(|x| (x - m).abs()).collect();
+    median(&deviations)
+}
+
+/// # Normalized median absolute deviation
+///
+/// `kirstine::median_absolute_deviation` scaled by `1.4826`, which makes it a consistent
+/// estimator of the standard deviation for normally-distributed data.
+///
+/// Panics if dataset is empty.
+pub fn median_absolute_deviation_normal(data: &[f64]) -> f64 {
+    const MAD_NORMAL_CONSTANT: f64 = 1.4826;
+    median_absolute_deviation(data) * MAD_NORMAL_CONSTANT
+}
+
 /// # Mode
 ///
 /// Finds the *mode* of a dataset.
@@ -76,7 +296,7 @@ pub fn median_from_sorted(data: &Vec<f64>) -> f64 {
 /// let data = vec![2.0, 5.0, 1.0, 3.0, 1.0];
 /// assert_eq!(kirstine::mode(&data), &1.0);
 /// ```
-pub fn mode(data: &Vec<f64>) -> &f64 {
+pub fn mode(data: &[f64]) -> &f64 {
     if data.is_empty() {
         panic!("Can not find mode of empty list");
     }
@@ -96,6 +316,10 @@ pub fn mode(data: &Vec<f64>) -> &f64 {
 /// The return value is a tuple of the range, the coefficient of range, and a tuple of largest and
 /// smallest value: `(range, coef_of_range, (&smallest, &largest))`
 ///
+/// Largest and smallest are found via `f64::total_cmp`, so a `NaN` in `data` is ordered
+/// consistently rather than silently swallowed by a `<`/`>` comparison that is always `false` for
+/// `NaN`; use `kirstine::try_range` if you need to detect that instead.
+///
 /// ## Example
 /// ```
 /// let data = vec![89.0, 73.0, 84.0, 91.0, 87.0, 77.0, 94.0];
@@ -105,17 +329,17 @@ pub fn mode(data: &Vec<f64>) -> &f64 {
 /// assert_eq!(smallest, &73.0);
 /// assert_eq!(largest, &94.0);
 /// ```
-pub fn range(data: &Vec<f64>) -> (f64, f64, (&f64, &f64)) {
+pub fn range(data: &[f64]) -> (f64, f64, (&f64, &f64)) {
     if data.is_empty() {
         panic!("Can not find range of empty list");
     }
     let mut largest = &data[0];
     let mut smallest = &data[0];
     for x in data.iter() {
-        if x > largest {
+        if x.total_cmp(largest) == Greater {
             largest = x;
         }
-        if x < smallest {
+        if x.total_cmp(smallest) == Less {
             smallest = x;
         }
     }
@@ -124,6 +348,19 @@ pub fn range(data: &Vec<f64>) -> (f64, f64, (&f64, &f64)) {
     (range, coef_of_range, (smallest, largest))
 }
 
+/// # Range, checked for `NaN`
+///
+/// Like `kirstine::range`, but returns `None` if `data` contains `NaN` instead of silently
+/// computing a meaningless statistic.
+///
+/// Panics if dataset is empty.
+pub fn try_range(data: &[f64]) -> Option<(f64, f64, (&f64, &f64))> {
+    if contains_nan(data) {
+        return None;
+    }
+    Some(range(data))
+}
+
 /**
  * # Pearson correlation coefficient
  *
@@ -134,7 +371,7 @@ pub fn range(data: &Vec<f64>) -> (f64, f64, (&f64, &f64)) {
  *
  * [Wikipedia article](https://en.wikipedia.org/wiki/Pearson_correlation_coefficient).
  */
-pub fn correlation(data: &Vec<(f64, f64)>) -> f64 {
+pub fn correlation(data: &[(f64, f64)]) -> f64 {
     let n = data.len() as f64;
     let mut sum_x = 0.0;
     let mut sum_y = 0.0;
@@ -175,8 +412,9 @@ fn correlation_test() {
 ///
 /// The sum, over all observations, of the squared differences of each observation from the overall
 /// mean.
-pub fn sum_of_squares(data: &Vec<f64>, mu: f64) -> f64 {
-    data.iter().map(|n| (n - mu).powf(2.0)).sum()
+pub fn sum_of_squares(data: &[f64], mu: f64) -> f64 {
+    let squares: Vec<f64> = data.iter().map(|n| (n - mu).powf(2.0)).collect();
+    sum(&squares)
 }
 
 /// # Chi-squared test
@@ -196,7 +434,7 @@ pub fn sum_of_squares(data: &Vec<f64>, mu: f64) -> f64 {
 /// ];
 /// assert_eq!(kirstine::chi_squared(&data), 1.91);
 /// ```
-pub fn chi_squared(data: &Vec<(f64, f64)>) -> f64 {
+pub fn chi_squared(data: &[(f64, f64)]) -> f64 {
     data.iter().map(|(e, o)| (e - o).powf(2.0) / e).sum()
 }
 
@@ -221,3 +459,52 @@ fn chi_squared_test() {
     let diff = (result - expected).abs();
     assert!(diff < 0.0000001);
 }
+
+#[test]
+fn median_orders_nan_deterministically_test() {
+    let data = vec![3.0, f64::NAN, 1.0, 2.0];
+    assert_eq!(median(&data), 2.5);
+}
+
+#[test]
+fn percentile_orders_nan_to_the_end_test() {
+    let data = vec![3.0, f64::NAN, 1.0, 2.0];
+    assert_eq!(percentile(&data, 0.0), 1.0);
+    assert!(percentile(&data, 100.0).is_nan());
+}
+
+#[test]
+fn range_orders_nan_deterministically_test() {
+    let data = vec![1.0, 2.0, f64::NAN];
+    let (_, _, (smallest, largest)) = range(&data);
+    assert_eq!(*smallest, 1.0);
+    assert!(largest.is_nan());
+}
+
+#[test]
+fn try_median_returns_none_for_nan_test() {
+    let data = vec![1.0, f64::NAN, 2.0];
+    assert_eq!(try_median(&data), None);
+    assert_eq!(try_median(&[1.0, 2.0, 3.0]), Some(2.0));
+}
+
+#[test]
+fn try_percentile_returns_none_for_nan_test() {
+    let data = vec![1.0, f64::NAN, 2.0];
+    assert_eq!(try_percentile(&data, 50.0), None);
+    assert_eq!(try_percentile(&[1.0, 2.0, 3.0], 50.0), Some(2.0));
+}
+
+#[test]
+fn try_quartiles_returns_none_for_nan_test() {
+    let data = vec![1.0, f64::NAN, 2.0, 3.0];
+    assert_eq!(try_quartiles(&data), None);
+    assert!(try_quartiles(&[1.0, 2.0, 3.0, 4.0]).is_some());
+}
+
+#[test]
+fn try_range_returns_none_for_nan_test() {
+    let data = vec![1.0, f64::NAN, 2.0];
+    assert_eq!(try_range(&data), None);
+    assert!(try_range(&[1.0, 2.0, 3.0]).is_some());
+}
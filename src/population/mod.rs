@@ -16,8 +16,8 @@
 /// let mu = kirstine::mean(&population);
 /// assert_eq!(kirstine::population::variance(&population, mu), 21704.0);
 /// ```
-pub fn variance(population: &Vec<f64>, mu: f64) -> f64 {
-    let tss = super::sum_of_squares(&population, mu);
+pub fn variance(population: &[f64], mu: f64) -> f64 {
+    let tss = super::sum_of_squares(population, mu);
     tss / population.len() as f64
 }
 
@@ -27,6 +27,6 @@ pub fn variance(population: &Vec<f64>, mu: f64) -> f64 {
 ///
 /// Again notice that that is a difference between `population::standard_deviation` and
 /// `sample::standard_deviation`, as they make use of different `variance` functions.
-pub fn standard_deviation(dataset: &Vec<f64>, mu: f64) -> f64 {
-    variance(&dataset, mu).sqrt()
+pub fn standard_deviation(dataset: &[f64], mu: f64) -> f64 {
+    variance(dataset, mu).sqrt()
 }
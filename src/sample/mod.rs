@@ -13,8 +13,8 @@
 /// let mu = kirstine::mean(&sample);
 /// assert_eq!(kirstine::sample::variance(&sample, mu), 27130.0);
 /// ```
-pub fn variance(sample: &Vec<f64>, mu: f64) -> f64 {
-    let tss = super::sum_of_squares(&sample, mu);
+pub fn variance(sample: &[f64], mu: f64) -> f64 {
+    let tss = super::sum_of_squares(sample, mu);
     tss / (sample.len() - 1) as f64
 }
 
@@ -24,8 +24,8 @@ pub fn variance(sample: &Vec<f64>, mu: f64) -> f64 {
 ///
 /// Again notice that that is a difference between `sample::standard_deviation` and
 /// `population::standard_deviation`, as they make use of different `variance` functions.
-pub fn standard_deviation(data: &Vec<f64>, mu: f64) -> f64 {
-    variance(&data, mu).sqrt()
+pub fn standard_deviation(data: &[f64], mu: f64) -> f64 {
+    variance(data, mu).sqrt()
 }
 
 pub fn z_score(mu_sample: f64, n_sample: usize, mu_population: f64, sigma_population: f64) -> f64 {
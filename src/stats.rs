@@ -0,0 +1,112 @@
+use std::cmp::Ordering::{Greater, Less};
+
+/// # Stats
+///
+/// A trait of summary statistics implemented for `[f64]`, mirroring Rust's libtest `Stats`
+/// trait. This lets any slice, array or `Vec<f64>` call `data.mean()`, `data.percentile(90.0)`,
+/// etc., instead of going through the free functions.
+///
+/// The free functions `kirstine::mean`, `kirstine::sum`, `kirstine::median`,
+/// `kirstine::percentile` and `kirstine::iqr` delegate to this trait; `Stats::mode` in turn calls
+/// `kirstine::mode`. The free functions remain the preferred entry point for users who don't want
+/// to import a trait.
+pub trait Stats {
+    /// See [`crate::mean`].
+    fn mean(&self) -> f64;
+    /// See [`crate::median`].
+    fn median(&self) -> f64;
+    /// The smallest value in the dataset. Panics if the dataset is empty.
+    fn min(&self) -> f64;
+    /// The largest value in the dataset. Panics if the dataset is empty.
+    fn max(&self) -> f64;
+    /// See [`crate::sum`].
+    fn sum(&self) -> f64;
+    /// The sample variance (divisor `N - 1`) of the dataset.
+    fn variance(&self) -> f64;
+    /// The sample standard deviation (the square root of [`Stats::variance`]).
+    fn std_dev(&self) -> f64;
+    /// See [`crate::percentile`].
+    fn percentile(&self, pct: f64) -> f64;
+    /// See [`crate::iqr`].
+    fn iqr(&self) -> f64;
+    /// See [`crate::mode`].
+    fn mode(&self) -> f64;
+}
+
+impl Stats for [f64] {
+    fn mean(&self) -> f64 {
+        self.sum() / self.len() as f64
+    }
+
+    fn median(&self) -> f64 {
+        let mut copy = self.to_vec();
+        crate::local_sort(&mut copy);
+        crate::median_from_sorted(&copy)
+    }
+
+    fn min(&self) -> f64 {
+        if self.is_empty() {
+            panic!("Can not find min of empty list");
+        }
+        let mut smallest = self[0];
+        for &x in self.iter() {
+            if x.total_cmp(&smallest) == Less {
+                smallest = x;
+            }
+        }
+        smallest
+    }
+
+    fn max(&self) -> f64 {
+        if self.is_empty() {
+            panic!("Can not find max of empty list");
+        }
+        let mut largest = self[0];
+        for &x in self.iter() {
+            if x.total_cmp(&largest) == Greater {
+                largest = x;
+            }
+        }
+        largest
+    }
+
+    fn sum(&self) -> f64 {
+        let mut sum = 0.0;
+        let mut c = 0.0;
+        for &x in self.iter() {
+            let t = sum + x;
+            if sum.abs() >= x.abs() {
+                c += (sum - t) + x;
+            } else {
+                c += (x - t) + sum;
+            }
+            sum = t;
+        }
+        sum + c
+    }
+
+    fn variance(&self) -> f64 {
+        let mu = self.mean();
+        let squares: Vec<f64> = self.iter().map(|n| (n - mu).powf(2.0)).collect();
+        squares.as_slice().sum() / (self.len() - 1) as f64
+    }
+
+    fn std_dev(&self) -> f64 {
+        self.variance().sqrt()
+    }
+
+    fn percentile(&self, pct: f64) -> f64 {
+        let mut copy = self.to_vec();
+        crate::local_sort(&mut copy);
+        crate::percentile_from_sorted(&copy, pct)
+    }
+
+    fn iqr(&self) -> f64 {
+        let (q1, _, q3) = crate::quartiles(self);
+        q3 - q1
+    }
+
+    fn mode(&self) -> f64 {
+        *crate::mode(self)
+    }
+}